@@ -124,16 +124,101 @@ use std::cell::RefCell;
 /// assert_eq!(None,    data_set.get_dim_size(DIM_NAME));
 /// assert_eq!(None,    data_set.get_dim_type(DIM_NAME));
 /// ```
+///
+/// ## Iterate over the dimensions in header order
+///
+/// ```
+/// use netcdf3::{DataSet, Dimension};
+///
+/// // First create a data set
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("dim_1", 10).unwrap();
+/// data_set.add_fixed_dim("dim_2", 20).unwrap();
+/// data_set.add_fixed_dim("dim_3", 30).unwrap();
+///
+/// // `dims()` yields the dimensions in the same order as the `dim_list` of the header.
+/// let names: Vec<String> = data_set.dims().map(|dim| dim.name()).collect();
+/// assert_eq!(vec!["dim_1", "dim_2", "dim_3"], names);
+/// ```
+///
+/// ## The *unlimited-size* dimension tracks the number of records written
+///
+/// The size of the *unlimited-size* dimension is not meant to be set directly: every `DataSet`
+/// operation that can change the number of records written to a record variable (appending
+/// through a [`RecordAppendWriter`](struct.RecordAppendWriter.html), or truncating through
+/// [`DataSet::truncate_records`](struct.DataSet.html#method.truncate_records)) calls
+/// [`DataSet::recompute_unlimited_dim_size`](struct.DataSet.html#method.recompute_unlimited_dim_size)
+/// afterwards, which refreshes the dimension through
+/// [`Dimension::set_unlimited_size`](#method.set_unlimited_size). This guarantee only covers
+/// record mutations that go through one of those two paths.
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// let unlim_dim = data_set.set_unlimited_dim("time", 0).unwrap();
+/// assert_eq!(0, unlim_dim.size());
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Dimension {
+    pub(in crate::data_set) id: DimensionId,
     pub(in crate::data_set) name: RefCell<String>,
     pub(in crate::data_set) size: DimensionSize,
 }
 
+/// Stable numeric identifier of a [`Dimension`](struct.Dimension.html) within its owning
+/// [`DataSet`](struct.DataSet.html).
+///
+/// The identifier is assigned once, when the dimension is created, from a counter the `DataSet`
+/// never rewinds: it matches the index the dimension has in the `dim_list` of the NetCDF-3
+/// header as long as no dimension has ever been removed from the data set, but keeps being
+/// unique even afterwards, since a removed dimension's id is never handed out again. It is
+/// returned by [`Dimension::id()`](struct.Dimension.html#method.id), used by
+/// [`DataSet::get_dim_by_id`](struct.DataSet.html#method.get_dim_by_id) and stays unchanged
+/// when the dimension is renamed through
+/// [`DataSet::rename_dim`](struct.DataSet.html#method.rename_dim).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// let id_1 = data_set.add_fixed_dim("dim_1", 10).unwrap().id();
+/// let id_2 = data_set.add_unlimited_dim("dim_2").unwrap().id();
+///
+/// data_set.rename_dim("dim_1", "renamed_dim_1").unwrap();
+///
+/// assert_eq!(id_1, data_set.get_dim("renamed_dim_1").unwrap().id());
+/// assert_eq!(data_set.get_dim_by_id(id_2).unwrap().name(), "dim_2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DimensionId(usize);
+
+impl DimensionId {
+    /// Creates the identifier holding the raw value `index` of the owning `DataSet`'s
+    /// next-id counter.
+    pub(in crate::data_set) fn new(index: usize) -> DimensionId {
+        return DimensionId(index);
+    }
+
+    /// Returns the raw value of the identifier.
+    pub(in crate::data_set) fn get_index(&self) -> usize {
+        return self.0;
+    }
+}
+
 /// Internal representation of the size of a dimension.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(in crate::data_set) enum DimensionSize {
-    /// *Unlimited-size* dimension, the unlimited size can be modifed by the NetCDF-3 dataset.
+    /// *Unlimited-size* dimension.
+    ///
+    /// The stored `usize` is a cache of the number of records currently present in the record
+    /// variables of the owning [`DataSet`](struct.DataSet.html). It is not authoritative by
+    /// itself: the `DataSet` recomputes and refreshes it, through
+    /// [`Dimension::set_unlimited_size`](struct.Dimension.html#method.set_unlimited_size),
+    /// every time the record variables are modified, so that it matches the actual number of
+    /// records written after each such change. Until the first refresh it holds the size given
+    /// when the dimension was created.
     Unlimited(RefCell<usize>),
     /// *Fixed-size* dimension
     Fixed(usize),
@@ -178,23 +263,33 @@ impl DimensionSize {
 impl Dimension {
 
     /// Creates a new *fixed size* NetCDF-3 dimension.
-    pub(in crate::data_set) fn new_fixed_size(name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
+    pub(in crate::data_set) fn new_fixed_size(id: DimensionId, name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
         Dimension::check_dim_name(name)?;
         return Ok(Dimension {
+            id,
             name: RefCell::new(name.to_string()),
             size: DimensionSize::new(size, DimensionType::FixedSize),
         });
     }
 
     /// Creates a new *unlimited size* NetCDF-3 dimension.
-    pub(in crate::data_set) fn new_unlimited_size(name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
+    pub(in crate::data_set) fn new_unlimited_size(id: DimensionId, name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
         Dimension::check_dim_name(name)?;
         return Ok(Dimension {
+            id,
             name: RefCell::new(name.to_string()),
             size: DimensionSize::new(size, DimensionType::UnlimitedSize),
         });
     }
 
+    /// Returns the stable numeric identifier of the NetCDF-3 dimension.
+    ///
+    /// The identifier is assigned when the dimension is created and remains unchanged across
+    /// a call to [`DataSet::rename_dim`](struct.DataSet.html#method.rename_dim).
+    pub fn id(&self) -> DimensionId {
+        return self.id;
+    }
+
     /// Returns the name of the NetCDF-3 dimension.
     pub fn name(&self) -> String {
         return self.name.borrow().clone();
@@ -226,6 +321,35 @@ impl Dimension {
             false => Err(InvalidDataSet::DimensionNameNotValid(dim_name.to_string())),
         };
     }
+
+    /// Refreshes the cached size of the *unlimited-size* dimension with `num_records`.
+    ///
+    /// Called by the owning [`DataSet`](struct.DataSet.html) after it has recomputed the number
+    /// of records from its record variables (see
+    /// [`Dimension::check_record_counts_match`](struct.Dimension.html#method.check_record_counts_match)),
+    /// so that [`Dimension::size`](#method.size) keeps reporting the actual number of records
+    /// written. Has no effect on a *fixed-size* dimension.
+    pub(in crate::data_set) fn set_unlimited_size(&self, num_records: usize) {
+        if let DimensionSize::Unlimited(size) = &self.size {
+            *size.borrow_mut() = num_records;
+        }
+    }
+
+    /// Checks that every record variable of a `DataSet` reports the same number of records.
+    ///
+    /// Returns the shared record count shared by all `record_counts`, or
+    /// `InvalidDataSet::UnlimitedDimRecordsMismatch` if they disagree. An empty slice of
+    /// `record_counts` (no record variable defined yet) returns `0`.
+    pub(in crate::data_set) fn check_record_counts_match(record_counts: &[usize]) -> Result<usize, InvalidDataSet> {
+        let first: usize = match record_counts.first() {
+            None => return Ok(0),
+            Some(first) => *first,
+        };
+        return match record_counts.iter().all(|count| *count == first) {
+            true => Ok(first),
+            false => Err(InvalidDataSet::UnlimitedDimRecordsMismatch(record_counts.to_vec())),
+        };
+    }
 }
 
 
@@ -234,14 +358,16 @@ mod tests {
 
     use std::rc::Rc;
     use crate::{Dimension, DimensionType};
+    use super::DimensionId;
 
     #[test]
     fn test_dim_new_fixed_size() {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
 
-        let dim = Dimension::new_fixed_size(DIM_NAME, DIM_SIZE).unwrap();
+        let dim = Dimension::new_fixed_size(DimensionId::new(0), DIM_NAME, DIM_SIZE).unwrap();
 
+        assert_eq!(DimensionId::new(0), dim.id());
         assert_eq!(DIM_NAME, dim.name());
         assert_eq!(DIM_SIZE, dim.size());
         assert_eq!(DimensionType::FixedSize, dim.dim_type());
@@ -254,8 +380,9 @@ mod tests {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
 
-        let dim = Dimension::new_unlimited_size(DIM_NAME, DIM_SIZE).unwrap();
+        let dim = Dimension::new_unlimited_size(DimensionId::new(0), DIM_NAME, DIM_SIZE).unwrap();
 
+        assert_eq!(DimensionId::new(0), dim.id());
         assert_eq!(DIM_NAME, dim.name());
         assert_eq!(DIM_SIZE, dim.size());
         assert_eq!(DimensionType::UnlimitedSize, dim.dim_type());
@@ -263,55 +390,76 @@ mod tests {
         assert_eq!(true, dim.is_unlimited());
     }
 
+    #[test]
+    fn test_dim_id_stable_across_rename() {
+        const DIM_NAME: &str = "dim_1";
+        const DIM_SIZE: usize = 10;
+
+        let dim = Dimension::new_fixed_size(DimensionId::new(3), DIM_NAME, DIM_SIZE).unwrap();
+        assert_eq!(DimensionId::new(3), dim.id());
+
+        *dim.name.borrow_mut() = "renamed_dim".to_string();
+
+        assert_eq!(DimensionId::new(3), dim.id());
+        assert_eq!("renamed_dim", dim.name());
+    }
+
     #[test]
     fn test_dim_equality() {
 
         // test equality between 2 fixed-size dimension
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
             assert_eq!(dim_a, dim_b);
         }
 
         // test equality between 2 fixed-size dimension with different sizes
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 90).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 90).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 fixed-size dimension with different names
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_2", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_2", 180).unwrap();
+            assert_ne!(dim_a, dim_b);
+        }
+
+        // test equality between 2 fixed-size dimension with different ids
+        {
+            let dim_a: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size(DimensionId::new(1), "name_1", 180).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 180).unwrap();
             assert_eq!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension with different sizes
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 90).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 90).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 180).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension with different names
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_2", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_2", 180).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 1 unlimited-size dimension and 1 fixed-size dimension
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size(DimensionId::new(0), "name_1", 180).unwrap();
             assert_ne!(dim_a, dim_b);
         }
     }
@@ -320,8 +468,8 @@ mod tests {
     fn test_rc_dim_equality() {
         // test equality between 2 fixed-size dimension
         {
-            let dim_a: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180).unwrap());
-            let dim_b: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180).unwrap());
+            let dim_a: Rc<Dimension> = Rc::new(Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap());
+            let dim_b: Rc<Dimension> = Rc::new(Dimension::new_fixed_size(DimensionId::new(0), "name_1", 180).unwrap());
 
             assert_eq!(dim_a, dim_b);
             assert!(!Rc::ptr_eq(&dim_a, &dim_b));
@@ -333,4 +481,33 @@ mod tests {
             assert!(!Rc::ptr_eq(&dim_b, &dim_c));
         }
     }
+
+    #[test]
+    fn test_dim_set_unlimited_size() {
+        let dim = Dimension::new_unlimited_size(DimensionId::new(0), "time", 0).unwrap();
+        assert_eq!(0, dim.size());
+
+        dim.set_unlimited_size(3);
+        assert_eq!(3, dim.size());
+
+        dim.set_unlimited_size(5);
+        assert_eq!(5, dim.size());
+    }
+
+    #[test]
+    fn test_dim_set_unlimited_size_has_no_effect_on_fixed_size() {
+        let dim = Dimension::new_fixed_size(DimensionId::new(0), "dim_1", 10).unwrap();
+
+        dim.set_unlimited_size(3);
+
+        assert_eq!(10, dim.size());
+    }
+
+    #[test]
+    fn test_dim_check_record_counts_match() {
+        assert_eq!(0, Dimension::check_record_counts_match(&[]).unwrap());
+        assert_eq!(3, Dimension::check_record_counts_match(&[3]).unwrap());
+        assert_eq!(3, Dimension::check_record_counts_match(&[3, 3, 3]).unwrap());
+        assert!(Dimension::check_record_counts_match(&[3, 4]).is_err());
+    }
 }
\ No newline at end of file
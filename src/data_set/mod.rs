@@ -0,0 +1,329 @@
+pub mod dimension;
+pub mod record_writer;
+pub mod variable;
+
+pub use dimension::{Dimension, DimensionId, DimensionType};
+pub use record_writer::RecordAppendWriter;
+pub use variable::{DataType, DataVector, Variable};
+
+use std::rc::Rc;
+
+use crate::InvalidDataSet;
+
+/// A NetCDF-3 data set: an ordered collection of [`Dimension`](struct.Dimension.html)s and
+/// [`Variable`](struct.Variable.html)s.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSet {
+    dims: Vec<Rc<Dimension>>,
+    unlimited_dim_id: Option<DimensionId>,
+    /// Identifier the next dimension added will be given. Monotonically increasing and never
+    /// reused, so that a dimension's id stays unique even after an earlier dimension is removed.
+    next_dim_id: usize,
+    vars: Vec<Rc<Variable>>,
+}
+
+impl DataSet {
+    /// Creates a new, empty data set.
+    pub fn new() -> DataSet {
+        return DataSet {
+            dims: Vec::new(),
+            unlimited_dim_id: None,
+            next_dim_id: 0,
+            vars: Vec::new(),
+        };
+    }
+
+    /// Returns the number of dimensions defined in the data set.
+    pub fn num_dims(&self) -> usize {
+        return self.dims.len();
+    }
+
+    /// Returns `true` if a dimension named `name` is defined in the data set.
+    pub fn has_dim(&self, name: &str) -> bool {
+        return self.get_dim(name).is_some();
+    }
+
+    /// Returns `true` if the data set has an *unlimited-size* dimension.
+    pub fn has_unlimited_dim(&self) -> bool {
+        return self.unlimited_dim_id.is_some();
+    }
+
+    /// Returns the dimension named `name`, if it is defined.
+    pub fn get_dim(&self, name: &str) -> Option<Rc<Dimension>> {
+        return self.dims.iter().find(|dim| dim.name() == name).map(Rc::clone);
+    }
+
+    /// Returns the dimension with the stable identifier `id`, if it is defined.
+    ///
+    /// Unlike [`get_dim`](#method.get_dim), this lookup keeps working across a rename, since
+    /// `id` never changes once a dimension has been created.
+    pub fn get_dim_by_id(&self, id: DimensionId) -> Option<Rc<Dimension>> {
+        return self.dims.iter().find(|dim| dim.id() == id).map(Rc::clone);
+    }
+
+    /// Returns the size of the dimension named `name`, if it is defined.
+    pub fn get_dim_size(&self, name: &str) -> Option<usize> {
+        return self.get_dim(name).map(|dim| dim.size());
+    }
+
+    /// Returns the type of the dimension named `name`, if it is defined.
+    pub fn get_dim_type(&self, name: &str) -> Option<DimensionType> {
+        return self.get_dim(name).map(|dim| dim.dim_type());
+    }
+
+    /// Returns the *unlimited-size* dimension, if one is defined.
+    pub fn get_unlimited_dim(&self) -> Option<Rc<Dimension>> {
+        return self.unlimited_dim_id.and_then(|id| self.get_dim_by_id(id));
+    }
+
+    /// Returns an iterator over the dimensions, in the same order as the `dim_list` of the
+    /// NetCDF-3 header, i.e. in id order. No writer serializing a `dim_list` from a `DataSet`
+    /// exists yet in this crate; this ordering is what such a writer would need to reproduce the
+    /// original layout.
+    pub fn dims(&self) -> impl Iterator<Item = Rc<Dimension>> + '_ {
+        return self.dims.iter().map(Rc::clone);
+    }
+
+    fn check_new_dim_name(&self, name: &str) -> Result<(), InvalidDataSet> {
+        Dimension::check_dim_name(name)?;
+        if self.has_dim(name) {
+            return Err(InvalidDataSet::DimensionAlreadyExists(name.to_string()));
+        }
+        return Ok(());
+    }
+
+    /// Returns the id the next dimension added to the data set will be given, and reserves it so
+    /// it is never handed out again.
+    fn next_dim_id(&mut self) -> DimensionId {
+        let id: DimensionId = DimensionId::new(self.next_dim_id);
+        self.next_dim_id += 1;
+        return id;
+    }
+
+    /// Adds a new *fixed-size* dimension named `name`.
+    pub fn add_fixed_dim(&mut self, name: &str, size: usize) -> Result<Rc<Dimension>, InvalidDataSet> {
+        self.check_new_dim_name(name)?;
+        let id: DimensionId = self.next_dim_id();
+        let dim: Rc<Dimension> = Rc::new(Dimension::new_fixed_size(id, name, size)?);
+        self.dims.push(Rc::clone(&dim));
+        return Ok(dim);
+    }
+
+    /// Sets the *unlimited-size* dimension of the data set, named `name` and initialized with
+    /// `size` records.
+    pub fn set_unlimited_dim(&mut self, name: &str, size: usize) -> Result<Rc<Dimension>, InvalidDataSet> {
+        self.check_new_dim_name(name)?;
+        if self.has_unlimited_dim() {
+            return Err(InvalidDataSet::UnlimitedDimAlreadyExists(name.to_string()));
+        }
+        let id: DimensionId = self.next_dim_id();
+        let dim: Rc<Dimension> = Rc::new(Dimension::new_unlimited_size(id, name, size)?);
+        self.dims.push(Rc::clone(&dim));
+        self.unlimited_dim_id = Some(id);
+        return Ok(dim);
+    }
+
+    /// Adds the *unlimited-size* dimension of the data set, named `name`, with zero records.
+    pub fn add_unlimited_dim(&mut self, name: &str) -> Result<Rc<Dimension>, InvalidDataSet> {
+        return self.set_unlimited_dim(name, 0);
+    }
+
+    /// Renames the dimension named `old_name` to `new_name`.
+    ///
+    /// The dimension keeps its [`DimensionId`](struct.DimensionId.html).
+    pub fn rename_dim(&mut self, old_name: &str, new_name: &str) -> Result<(), InvalidDataSet> {
+        Dimension::check_dim_name(new_name)?;
+        if self.has_dim(new_name) {
+            return Err(InvalidDataSet::DimensionAlreadyExists(new_name.to_string()));
+        }
+        let dim: Rc<Dimension> = self
+            .get_dim(old_name)
+            .ok_or_else(|| InvalidDataSet::DimensionNotDefined(old_name.to_string()))?;
+        *dim.name.borrow_mut() = new_name.to_string();
+        return Ok(());
+    }
+
+    /// Removes and returns the dimension named `name`.
+    pub fn remove_dim(&mut self, name: &str) -> Result<Rc<Dimension>, InvalidDataSet> {
+        let index: usize = self
+            .dims
+            .iter()
+            .position(|dim| dim.name() == name)
+            .ok_or_else(|| InvalidDataSet::DimensionNotDefined(name.to_string()))?;
+        let removed: Rc<Dimension> = self.dims.remove(index);
+        if self.unlimited_dim_id == Some(removed.id()) {
+            self.unlimited_dim_id = None;
+        }
+        return Ok(removed);
+    }
+
+    /// Returns an iterator over the variables, in `var_list` order.
+    pub fn vars(&self) -> impl Iterator<Item = Rc<Variable>> + '_ {
+        return self.vars.iter().map(Rc::clone);
+    }
+
+    /// Returns the variable named `name`, if it is defined.
+    pub fn get_var(&self, name: &str) -> Option<Rc<Variable>> {
+        return self.vars.iter().find(|var| var.name() == name).map(Rc::clone);
+    }
+
+    /// Appends `var` to the data set, after the variables already defined, and returns a shared
+    /// reference to it.
+    pub(in crate::data_set) fn push_var(&mut self, var: Variable) -> Rc<Variable> {
+        let var: Rc<Variable> = Rc::new(var);
+        self.vars.push(Rc::clone(&var));
+        return var;
+    }
+
+    /// Recomputes the size of the *unlimited-size* dimension from the record variables.
+    ///
+    /// This is the `DataSet`-driven recompute path: every operation that can change the number
+    /// of records written to a record variable (appending through a
+    /// [`RecordAppendWriter`](struct.RecordAppendWriter.html), or truncating through
+    /// [`truncate_records`](#method.truncate_records)) calls this method afterwards, so that
+    /// [`Dimension::size`](struct.Dimension.html#method.size) of the unlimited dimension is never
+    /// read before it has been refreshed. Does nothing if the data set has no unlimited
+    /// dimension.
+    pub(in crate::data_set) fn recompute_unlimited_dim_size(&self) -> Result<(), InvalidDataSet> {
+        let unlimited_dim: Rc<Dimension> = match self.get_unlimited_dim() {
+            None => return Ok(()),
+            Some(dim) => dim,
+        };
+        let record_counts: Vec<usize> = self.vars.iter().filter(|var| var.is_record_var()).map(|var| var.num_records()).collect();
+        let num_records: usize = Dimension::check_record_counts_match(&record_counts)?;
+        unlimited_dim.set_unlimited_size(num_records);
+        return Ok(());
+    }
+
+    /// Truncates every record variable down to `new_num_records` records and refreshes the
+    /// *unlimited-size* dimension accordingly.
+    ///
+    /// Record variables that already have fewer records are left untouched. Does nothing if the
+    /// data set has no unlimited dimension.
+    pub fn truncate_records(&mut self, new_num_records: usize) -> Result<(), InvalidDataSet> {
+        if !self.has_unlimited_dim() {
+            return Ok(());
+        }
+        for var in self.vars.iter().filter(|var| var.is_record_var()) {
+            if var.num_records() > new_num_records {
+                var.set_num_records(new_num_records);
+            }
+        }
+        return self.recompute_unlimited_dim_size();
+    }
+}
+
+impl Default for DataSet {
+    fn default() -> DataSet {
+        return DataSet::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::DataSet;
+
+    #[test]
+    fn test_data_set_get_dim_by_id_survives_rename() {
+        let mut data_set = DataSet::new();
+        let id_1 = data_set.add_fixed_dim("dim_1", 10).unwrap().id();
+        let id_2 = data_set.add_unlimited_dim("dim_2").unwrap().id();
+
+        data_set.rename_dim("dim_1", "renamed_dim_1").unwrap();
+
+        assert_eq!(id_1, data_set.get_dim("renamed_dim_1").unwrap().id());
+        assert_eq!("dim_2", data_set.get_dim_by_id(id_2).unwrap().name());
+        assert_eq!(None, data_set.get_dim("dim_1").map(|dim| dim.id()));
+    }
+
+    #[test]
+    fn test_data_set_dims_are_in_id_order() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("dim_1", 10).unwrap();
+        data_set.add_fixed_dim("dim_2", 20).unwrap();
+        data_set.add_fixed_dim("dim_3", 30).unwrap();
+
+        let names: Vec<String> = data_set.dims().map(|dim| dim.name()).collect();
+        assert_eq!(vec!["dim_1", "dim_2", "dim_3"], names);
+
+        let ids: Vec<usize> = data_set.dims().map(|dim| dim.id().get_index()).collect();
+        assert_eq!(vec![0, 1, 2], ids);
+    }
+
+    #[test]
+    fn test_data_set_dim_ids_are_not_reused_after_remove() {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("dim_a", 10).unwrap();
+        data_set.add_fixed_dim("dim_b", 20).unwrap();
+        let id_c = data_set.add_fixed_dim("dim_c", 30).unwrap().id();
+
+        data_set.remove_dim("dim_a").unwrap();
+        let id_d = data_set.add_fixed_dim("dim_d", 40).unwrap().id();
+
+        assert_ne!(id_c, id_d);
+        assert_eq!("dim_c", data_set.get_dim_by_id(id_c).unwrap().name());
+        assert_eq!("dim_d", data_set.get_dim_by_id(id_d).unwrap().name());
+    }
+
+    #[test]
+    fn test_data_set_remove_dim_clears_unlimited_dim() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("dim_1", 10).unwrap();
+
+        assert_eq!(true, data_set.has_unlimited_dim());
+        let _removed = data_set.remove_dim("dim_1").unwrap();
+        assert_eq!(false, data_set.has_unlimited_dim());
+        assert_eq!(None, data_set.get_unlimited_dim());
+    }
+
+    #[test]
+    fn test_data_set_recompute_unlimited_dim_size_from_records() {
+        use super::{DataType, Variable};
+
+        let mut data_set = DataSet::new();
+        let unlim_dim = data_set.set_unlimited_dim("time", 0).unwrap();
+        let var = data_set.push_var(Variable::new("temperature", vec![unlim_dim.clone()], DataType::F32));
+
+        assert_eq!(0, unlim_dim.size());
+
+        var.set_num_records(3);
+        data_set.recompute_unlimited_dim_size().unwrap();
+        assert_eq!(3, unlim_dim.size());
+    }
+
+    #[test]
+    fn test_data_set_recompute_unlimited_dim_size_rejects_mismatch() {
+        use super::{DataType, Variable};
+
+        let mut data_set = DataSet::new();
+        let unlim_dim = data_set.set_unlimited_dim("time", 0).unwrap();
+        let var_1 = data_set.push_var(Variable::new("temperature", vec![unlim_dim.clone()], DataType::F32));
+        let var_2 = data_set.push_var(Variable::new("pressure", vec![unlim_dim.clone()], DataType::F32));
+
+        var_1.set_num_records(3);
+        var_2.set_num_records(4);
+
+        assert!(data_set.recompute_unlimited_dim_size().is_err());
+    }
+
+    #[test]
+    fn test_data_set_truncate_records() {
+        use super::{DataType, Variable};
+
+        let mut data_set = DataSet::new();
+        let unlim_dim = data_set.set_unlimited_dim("time", 0).unwrap();
+        let var = data_set.push_var(Variable::new("temperature", vec![unlim_dim.clone()], DataType::F32));
+        var.set_num_records(5);
+        data_set.recompute_unlimited_dim_size().unwrap();
+        assert_eq!(5, unlim_dim.size());
+
+        data_set.truncate_records(2).unwrap();
+        assert_eq!(2, unlim_dim.size());
+        assert_eq!(2, var.num_records());
+
+        // Truncating to a larger size than the current number of records has no effect.
+        data_set.truncate_records(100).unwrap();
+        assert_eq!(2, unlim_dim.size());
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::error::WriteError;
+use crate::{DataSet, DataVector, Dimension, InvalidDataSet};
+
+/// Byte offset, from the start of the file, of the `numrecs` field of the NetCDF-3 header.
+const NUMRECS_OFFSET: u64 = 4;
+
+/// All NetCDF-3 values are padded to a multiple of 4 bytes, except the record stride of the sole
+/// record variable of a file, which the classic format leaves unpadded.
+const PADDING_UNIT: usize = 4;
+
+/// Returns `num_bytes` rounded up to the next multiple of [`PADDING_UNIT`](constant.PADDING_UNIT.html).
+#[inline]
+fn padded_size(num_bytes: usize) -> usize {
+    return num_bytes.div_ceil(PADDING_UNIT) * PADDING_UNIT;
+}
+
+/// Writer appending new records to the record (*unlimited-dimension*) variables of an
+/// already-written NetCDF-3 file, without rewriting the whole file.
+///
+/// NetCDF-3 stores the data of every record variable interleaved, one stride per record, right
+/// after the data of the fixed-size variables. `RecordAppendWriter` relies on this layout:
+/// appending a record only requires (1) seeking to the current end of the record section,
+/// (2) writing the new stride, (3) telling the [`DataSet`](struct.DataSet.html) that one more
+/// record was written, so it can recompute the *unlimited-size*
+/// [`Dimension`](struct.Dimension.html) through
+/// [`DataSet::recompute_unlimited_dim_size`](struct.DataSet.html#method.recompute_unlimited_dim_size),
+/// and (4) patching the `numrecs` field of the header in place. A long time series can therefore
+/// be built one record at a time, without ever holding the whole dataset in memory.
+///
+/// When a file defines a single record variable, the classic format does not pad its record
+/// stride to a multiple of 4 bytes; `RecordAppendWriter` accounts for this exception both when
+/// computing the stride and when writing each record.
+///
+/// `open` takes the `data_set` and `record_section_offset` already parsed from `file_path`'s
+/// header, rather than parsing the header itself; this crate does not implement that parsing
+/// yet.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use netcdf3::data_set::record_writer::RecordAppendWriter;
+/// use netcdf3::{DataSet, DataVector};
+///
+/// // In a real program, `data_set` and `record_section_offset` come from parsing the header of
+/// // the file being appended to.
+/// # let data_set = DataSet::new();
+/// # let record_section_offset = 0_u64;
+/// let mut writer = RecordAppendWriter::open("temp_3d.nc", data_set, record_section_offset).unwrap();
+/// for temperature in vec![21.0_f32, 21.4, 22.1] {
+///     let mut record: HashMap<String, DataVector> = HashMap::new();
+///     record.insert("temperature".to_string(), DataVector::F32(vec![temperature]));
+///     writer.append_record(&record).unwrap();
+/// }
+/// ```
+pub struct RecordAppendWriter {
+    file: File,
+    data_set: DataSet,
+    unlim_dim: Rc<Dimension>,
+    /// Record variables, in the order they appear in the `var_list` of the header.
+    record_var_names: Vec<String>,
+    /// Byte offset of the first record stride, i.e. the `begin` offset of the first record
+    /// variable.
+    record_section_offset: u64,
+    /// Total, already padded, number of bytes written to the file for one record.
+    record_stride_bytes: usize,
+    /// `true` if the data set defines exactly one record variable, in which case its stride is
+    /// not padded to a multiple of 4 bytes.
+    is_single_record_var: bool,
+}
+
+impl RecordAppendWriter {
+    /// Prepares `file_path` to receive new records appended to its record variables.
+    ///
+    /// `data_set` and `record_section_offset` must have already been parsed from `file_path`'s
+    /// own NetCDF-3 header; this crate does not parse NetCDF-3 headers itself yet.
+    ///
+    /// Returns `WriteError::NoRecordVariable` if `data_set` does not define any record
+    /// (*unlimited-dimension*) variable.
+    pub fn open<P: AsRef<Path>>(
+        file_path: P,
+        data_set: DataSet,
+        record_section_offset: u64,
+    ) -> Result<RecordAppendWriter, WriteError> {
+        let unlim_dim: Rc<Dimension> = data_set
+            .get_unlimited_dim()
+            .ok_or(WriteError::NoRecordVariable)?;
+
+        let record_var_names: Vec<String> = data_set
+            .vars()
+            .filter(|var| var.is_record_var())
+            .map(|var| var.name())
+            .collect();
+        if record_var_names.is_empty() {
+            return Err(WriteError::NoRecordVariable);
+        }
+        let is_single_record_var: bool = record_var_names.len() == 1;
+
+        let record_stride_bytes: usize = record_var_names
+            .iter()
+            .map(|var_name| {
+                let raw_stride_bytes: usize = data_set.get_var(var_name).unwrap().record_stride_bytes();
+                return if is_single_record_var {
+                    raw_stride_bytes
+                } else {
+                    padded_size(raw_stride_bytes)
+                };
+            })
+            .sum();
+
+        let file: File = OpenOptions::new().read(true).write(true).open(file_path.as_ref())?;
+
+        return Ok(RecordAppendWriter {
+            file,
+            data_set,
+            unlim_dim,
+            record_var_names,
+            record_section_offset,
+            record_stride_bytes,
+            is_single_record_var,
+        });
+    }
+
+    /// Returns the number of records already written to the record variables.
+    #[inline]
+    pub fn num_records(&self) -> usize {
+        return self.unlim_dim.size();
+    }
+
+    /// Appends one record to every record variable of the data set.
+    ///
+    /// `record` must hold exactly one entry per record variable, each matching the variable's
+    /// non-record shape, otherwise an `InvalidDataSet` error is returned and the file is left
+    /// untouched. Can be called repeatedly to append as many records as needed.
+    pub fn append_record(&mut self, record: &HashMap<String, DataVector>) -> Result<(), WriteError> {
+        // First validate every record variable's slice, before writing anything to the file.
+        for var_name in self.record_var_names.iter() {
+            let var = self.data_set.get_var(var_name).unwrap();
+            let data: &DataVector = record
+                .get(var_name)
+                .ok_or_else(|| InvalidDataSet::VariableNotDefined(var_name.clone()))?;
+            if data.len() != var.non_record_len() {
+                return Err(InvalidDataSet::VariableMismatchDataLength {
+                    var_name: var_name.clone(),
+                    req: var.non_record_len(),
+                    get: data.len(),
+                }
+                .into());
+            }
+        }
+
+        let record_offset: u64 =
+            self.record_section_offset + (self.num_records() as u64) * (self.record_stride_bytes as u64);
+        self.file.seek(SeekFrom::Start(record_offset))?;
+        for var_name in self.record_var_names.iter() {
+            let data: &DataVector = &record[var_name];
+            let raw_bytes: Vec<u8> = data.to_bytes();
+            self.file.write_all(&raw_bytes)?;
+            if !self.is_single_record_var {
+                let padding: usize = padded_size(raw_bytes.len()) - raw_bytes.len();
+                if padding > 0 {
+                    self.file.write_all(&vec![0_u8; padding])?;
+                }
+            }
+        }
+
+        for var_name in self.record_var_names.iter() {
+            let var = self.data_set.get_var(var_name).unwrap();
+            var.set_num_records(var.num_records() + 1);
+        }
+        self.data_set.recompute_unlimited_dim_size()?;
+
+        let new_num_records: usize = self.num_records();
+        self.file.seek(SeekFrom::Start(NUMRECS_OFFSET))?;
+        self.file.write_all(&(new_num_records as u32).to_be_bytes())?;
+        self.file.flush()?;
+
+        return Ok(());
+    }
+}
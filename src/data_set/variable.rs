@@ -0,0 +1,206 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::data_set::dimension::Dimension;
+
+/// NetCDF-3 external data type of a [`Variable`](struct.Variable.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DataType {
+    I8 = 1,
+    U8 = 2,
+    I16 = 3,
+    I32 = 4,
+    F32 = 5,
+    F64 = 6,
+}
+
+impl DataType {
+    /// Returns the number of bytes taken by one element of this type, on disk.
+    #[inline]
+    pub fn size_bytes(&self) -> usize {
+        return match self {
+            DataType::I8 | DataType::U8 => 1,
+            DataType::I16 => 2,
+            DataType::I32 | DataType::F32 => 4,
+            DataType::F64 => 8,
+        };
+    }
+}
+
+/// Owned, strongly-typed data for one variable (or one record of a record variable).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataVector {
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+impl DataVector {
+    /// Returns the number of elements held.
+    pub fn len(&self) -> usize {
+        return match self {
+            DataVector::I8(data) => data.len(),
+            DataVector::U8(data) => data.len(),
+            DataVector::I16(data) => data.len(),
+            DataVector::I32(data) => data.len(),
+            DataVector::F32(data) => data.len(),
+            DataVector::F64(data) => data.len(),
+        };
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Returns the big-endian on-disk representation of the data, without padding.
+    pub(in crate::data_set) fn to_bytes(&self) -> Vec<u8> {
+        return match self {
+            DataVector::I8(data) => data.iter().map(|value| *value as u8).collect(),
+            DataVector::U8(data) => data.clone(),
+            DataVector::I16(data) => data.iter().flat_map(|value| value.to_be_bytes()).collect(),
+            DataVector::I32(data) => data.iter().flat_map(|value| value.to_be_bytes()).collect(),
+            DataVector::F32(data) => data.iter().flat_map(|value| value.to_be_bytes()).collect(),
+            DataVector::F64(data) => data.iter().flat_map(|value| value.to_be_bytes()).collect(),
+        };
+    }
+}
+
+/// NetCDF-3 variable.
+///
+/// `Variable` instances are managed by a [`DataSet`](struct.DataSet.html), the same way
+/// [`Dimension`](struct.Dimension.html) instances are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    pub(in crate::data_set) name: RefCell<String>,
+    pub(in crate::data_set) dims: Vec<Rc<Dimension>>,
+    pub(in crate::data_set) data_type: DataType,
+    /// Number of records already written for this variable, when it is a record variable.
+    pub(in crate::data_set) num_records: Cell<usize>,
+}
+
+impl Variable {
+    /// Creates a new variable, defined over `dims`, with zero records already written.
+    ///
+    /// If `dims` starts with the *unlimited-size* dimension, the variable is a *record variable*.
+    pub(in crate::data_set) fn new(name: &str, dims: Vec<Rc<Dimension>>, data_type: DataType) -> Variable {
+        return Variable {
+            name: RefCell::new(name.to_string()),
+            dims,
+            data_type,
+            num_records: Cell::new(0),
+        };
+    }
+
+    /// Returns the name of the variable.
+    pub fn name(&self) -> String {
+        return self.name.borrow().clone();
+    }
+
+    /// Returns the data type of the variable.
+    pub fn data_type(&self) -> DataType {
+        return self.data_type;
+    }
+
+    /// Returns the dimensions the variable is defined over, in declaration order.
+    pub fn dims(&self) -> &[Rc<Dimension>] {
+        return &self.dims;
+    }
+
+    /// Returns `true` if the variable is defined over the *unlimited-size* dimension, i.e. if it
+    /// is a record variable.
+    pub fn is_record_var(&self) -> bool {
+        return self.dims.first().is_some_and(|dim| dim.is_unlimited());
+    }
+
+    /// Returns the number of elements of one record (or of the whole variable, if it is not a
+    /// record variable), i.e. the product of the sizes of every dimension but the
+    /// *unlimited-size* one.
+    pub fn non_record_len(&self) -> usize {
+        let dims: &[Rc<Dimension>] = if self.is_record_var() { &self.dims[1..] } else { &self.dims[..] };
+        if dims.is_empty() {
+            // A scalar variable (no dimension) holds exactly one value.
+            return 1;
+        }
+        return dims.iter().map(|dim| dim.size()).product();
+    }
+
+    /// Returns the number of bytes taken by one record of the variable (or the whole variable,
+    /// if it is not a record variable), without padding.
+    pub fn record_stride_bytes(&self) -> usize {
+        return self.non_record_len() * self.data_type.size_bytes();
+    }
+
+    /// Returns the number of records already written for this variable.
+    ///
+    /// Stays `0` for a variable that is not a record variable, since the `DataSet` only updates
+    /// the count of record variables.
+    pub fn num_records(&self) -> usize {
+        return self.num_records.get();
+    }
+
+    /// Updates the number of records already written for this variable.
+    ///
+    /// Called by the owning [`DataSet`](struct.DataSet.html), after records are appended to or
+    /// truncated from the variable, so that the unlimited dimension can be recomputed.
+    pub(in crate::data_set) fn set_num_records(&self, num_records: usize) {
+        self.num_records.set(num_records);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+
+    use crate::data_set::dimension::{Dimension, DimensionId};
+    use super::{DataType, Variable};
+
+    #[test]
+    fn test_var_is_record_var() {
+        let unlim_dim = Rc::new(Dimension::new_unlimited_size(DimensionId::new(0), "time", 0).unwrap());
+        let fixed_dim = Rc::new(Dimension::new_fixed_size(DimensionId::new(1), "x", 10).unwrap());
+
+        let record_var = Variable::new("temperature", vec![Rc::clone(&unlim_dim), Rc::clone(&fixed_dim)], DataType::F32);
+        assert_eq!(true, record_var.is_record_var());
+        assert_eq!(10, record_var.non_record_len());
+        assert_eq!(40, record_var.record_stride_bytes());
+
+        let fixed_var = Variable::new("x_coord", vec![Rc::clone(&fixed_dim)], DataType::F64);
+        assert_eq!(false, fixed_var.is_record_var());
+        assert_eq!(10, fixed_var.non_record_len());
+        assert_eq!(80, fixed_var.record_stride_bytes());
+    }
+
+    #[test]
+    fn test_var_scalar_non_record_len() {
+        let scalar_var = Variable::new("scalar", vec![], DataType::I32);
+        assert_eq!(1, scalar_var.non_record_len());
+    }
+
+    #[test]
+    fn test_var_set_num_records() {
+        let var = Variable::new("temperature", vec![], DataType::F32);
+        assert_eq!(0, var.num_records());
+        var.set_num_records(5);
+        assert_eq!(5, var.num_records());
+    }
+
+    #[test]
+    fn test_data_vector_len_and_bytes() {
+        use super::DataVector;
+
+        let data = DataVector::I32(vec![1, 2, 3]);
+        assert_eq!(3, data.len());
+        assert_eq!(false, data.is_empty());
+        assert_eq!(vec![0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3], data.to_bytes());
+
+        let empty = DataVector::F64(vec![]);
+        assert_eq!(0, empty.len());
+        assert_eq!(true, empty.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+
+/// Error returned when an operation would leave a [`DataSet`](data_set/struct.DataSet.html) in
+/// an invalid or inconsistent state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidDataSet {
+    /// The given dimension name is not a valid NetCDF-3 name.
+    DimensionNameNotValid(String),
+    /// No dimension with this name is defined in the data set.
+    DimensionNotDefined(String),
+    /// A dimension with this name already exists in the data set.
+    DimensionAlreadyExists(String),
+    /// The data set already has an *unlimited-size* dimension.
+    UnlimitedDimAlreadyExists(String),
+    /// The record variables of the data set do not all report the same number of records.
+    UnlimitedDimRecordsMismatch(Vec<usize>),
+    /// No variable with this name is defined in the data set.
+    VariableNotDefined(String),
+    /// The data given for a variable does not have the expected length.
+    VariableMismatchDataLength {
+        /// Name of the variable the data was provided for.
+        var_name: String,
+        /// Number of elements required (the variable's `non_record_len()`).
+        req: usize,
+        /// Number of elements actually provided.
+        get: usize,
+    },
+}
+
+impl fmt::Display for InvalidDataSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            InvalidDataSet::DimensionNameNotValid(name) => write!(f, "the dimension name `{}` is not valid", name),
+            InvalidDataSet::DimensionNotDefined(name) => write!(f, "the dimension `{}` is not defined", name),
+            InvalidDataSet::DimensionAlreadyExists(name) => write!(f, "the dimension `{}` already exists", name),
+            InvalidDataSet::UnlimitedDimAlreadyExists(name) => {
+                write!(f, "the data set already has an unlimited-size dimension, cannot add `{}`", name)
+            }
+            InvalidDataSet::UnlimitedDimRecordsMismatch(counts) => {
+                write!(f, "the record variables disagree on the number of records: {:?}", counts)
+            }
+            InvalidDataSet::VariableNotDefined(var_name) => write!(f, "the variable `{}` is not defined", var_name),
+            InvalidDataSet::VariableMismatchDataLength { var_name, req, get } => write!(
+                f,
+                "the variable `{}` requires {} elements, but {} were given",
+                var_name, req, get
+            ),
+        };
+    }
+}
+
+impl std::error::Error for InvalidDataSet {}
+
+/// Error returned while writing a NetCDF-3 file.
+#[derive(Debug)]
+pub enum WriteError {
+    /// An I/O error occurred while writing the file.
+    IOError(std::io::Error),
+    /// The requested write would leave the data set in an invalid state.
+    InvalidDataSet(InvalidDataSet),
+    /// The data set has no record variable to write records for.
+    NoRecordVariable,
+}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            WriteError::IOError(err) => write!(f, "I/O error: {}", err),
+            WriteError::InvalidDataSet(err) => write!(f, "invalid data set: {}", err),
+            WriteError::NoRecordVariable => write!(f, "the data set has no record variable"),
+        };
+    }
+}
+
+impl std::error::Error for WriteError {}
+
+impl From<std::io::Error> for WriteError {
+    fn from(err: std::io::Error) -> Self {
+        return WriteError::IOError(err);
+    }
+}
+
+impl From<InvalidDataSet> for WriteError {
+    fn from(err: InvalidDataSet) -> Self {
+        return WriteError::InvalidDataSet(err);
+    }
+}
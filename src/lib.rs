@@ -0,0 +1,8 @@
+//! Pure Rust implementation of the NetCDF-3 file format (classic and 64-bit offset).
+
+pub mod data_set;
+mod error;
+mod name_string;
+
+pub use data_set::{DataSet, DataType, DataVector, Dimension, DimensionId, DimensionType, RecordAppendWriter, Variable};
+pub use error::{InvalidDataSet, WriteError};
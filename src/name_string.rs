@@ -0,0 +1,13 @@
+/// Returns `true` if `name` is a valid NetCDF-3 name for a dimension, a variable or an attribute.
+///
+/// A valid name is non-empty, starts with a letter or an underscore, and otherwise only contains
+/// letters, digits, underscores, dots, `@`, `+` or `-`.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    let first_char_is_valid: bool = match chars.next() {
+        None => return false,
+        Some(first_char) => first_char.is_alphabetic() || first_char == '_',
+    };
+    return first_char_is_valid
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '@' | '+' | '-'));
+}